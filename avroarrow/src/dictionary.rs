@@ -0,0 +1,69 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::builder::{ArrayBuilder, Int32Builder};
+use arrow::array::{ArrayRef, DictionaryArray, StringArray};
+use arrow::datatypes::Int32Type;
+
+/// An [`ArrayBuilder`] for an Avro enum dictionary-encoded as
+/// `Dictionary(Int32, Utf8)`, whose dictionary values are exactly the
+/// enum's declared symbols in declaration order.
+///
+/// Since an Avro enum value already carries its symbol's index
+/// (`Value::Enum(idx, _)`), rows are appended as that index directly
+/// rather than re-interning the symbol string on every row.
+pub struct EnumDictionaryBuilder {
+    values: ArrayRef,
+    keys: Int32Builder,
+}
+
+impl EnumDictionaryBuilder {
+    pub fn new(symbols: &[String], cap: usize) -> Self {
+        Self {
+            values: Arc::new(StringArray::from(symbols.to_vec())),
+            keys: Int32Builder::with_capacity(cap),
+        }
+    }
+
+    pub fn append_index(&mut self, idx: i32) {
+        self.keys.append_value(idx);
+    }
+
+    pub fn append_null(&mut self) {
+        self.keys.append_null();
+    }
+}
+
+impl ArrayBuilder for EnumDictionaryBuilder {
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        let keys = self.keys.finish();
+        Arc::new(
+            DictionaryArray::<Int32Type>::try_new(keys, self.values.clone())
+                .expect("enum symbol indices must be in range"),
+        )
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        let keys = self.keys.finish_cloned();
+        Arc::new(
+            DictionaryArray::<Int32Type>::try_new(keys, self.values.clone())
+                .expect("enum symbol indices must be in range"),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
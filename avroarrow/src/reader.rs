@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+use apache_avro::schema::RecordSchema;
+use apache_avro::types::Value;
+use apache_avro::Schema;
+use arrow::array::builder::{ArrayBuilder, StructBuilder};
+use arrow::array::StructArray;
+use arrow::datatypes::SchemaRef;
+use arrow::error::ArrowError;
+use arrow::record_batch::{RecordBatch, RecordBatchReader as ArrowRecordBatchReader};
+
+use crate::record::{append_struct_field, finish_struct_row};
+use crate::schema::{convert_schema_projected, create_builder_projected, Projection};
+
+/// Drives a `Value` iterator (typically an `apache_avro::Reader` over an
+/// Object Container File) into fixed-size Arrow `RecordBatch` chunks,
+/// reusing the same field builders `append_record` already knows how to
+/// fill. Implements `arrow::record_batch::RecordBatchReader`, so it can be
+/// handed directly to anything that consumes one, such as a DataFusion or
+/// delta-rs writer.
+pub struct RecordBatchReader<I> {
+    values: I,
+    schema: RecordSchema,
+    arrow_schema: SchemaRef,
+    projection: Option<Projection>,
+    batch_size: usize,
+    builder: Box<dyn ArrayBuilder>,
+    done: bool,
+}
+
+impl<I> RecordBatchReader<I>
+where
+    I: Iterator<Item = Result<Value, apache_avro::Error>>,
+{
+    pub fn new(values: I, schema: Schema, batch_size: usize) -> Result<Self, Box<dyn Error>> {
+        Self::with_projection(values, schema, batch_size, None)
+    }
+
+    pub fn with_projection(
+        values: I,
+        schema: Schema,
+        batch_size: usize,
+        projection: Option<Projection>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let record_schema = match &schema {
+            Schema::Record(inner) => inner.clone(),
+            _ => return Err("RecordBatchReader requires a top-level record schema".into()),
+        };
+
+        let arrow_schema = Arc::new(convert_schema_projected(&schema, projection.as_ref())?);
+        let builder = create_builder_projected(&schema, batch_size, projection.as_ref())?;
+
+        Ok(Self {
+            values,
+            schema: record_schema,
+            arrow_schema,
+            projection,
+            batch_size,
+            builder,
+            done: false,
+        })
+    }
+
+    fn field_names(&self) -> Vec<&str> {
+        match &self.projection {
+            Some(projection) => projection.names().iter().map(String::as_str).collect(),
+            None => self.schema.fields.iter().map(|f| f.name.as_str()).collect(),
+        }
+    }
+
+    fn append(&mut self, value: &Value) -> Result<(), Box<dyn Error>> {
+        let typed = self
+            .builder
+            .as_any_mut()
+            .downcast_mut::<StructBuilder>()
+            .expect("a top-level record schema always builds a StructBuilder");
+
+        match value {
+            Value::Record(fields) => {
+                let by_name: HashMap<&str, &Value> =
+                    fields.iter().map(|(name, v)| (name.as_str(), v)).collect();
+
+                for (i, name) in self.field_names().into_iter().enumerate() {
+                    let field = self
+                        .schema
+                        .fields
+                        .iter()
+                        .find(|f| f.name == name)
+                        .ok_or_else(|| format!("field '{}': not found in schema", name))?;
+                    let field_value = by_name
+                        .get(name)
+                        .ok_or_else(|| format!("field '{}': missing from record", name))?;
+
+                    append_struct_field(typed, i, &field.schema, field_value)
+                        .map_err(|e| format!("field '{}': {}", name, e))?;
+                }
+
+                finish_struct_row(typed);
+                Ok(())
+            }
+            _ => Err(format!("expected a top-level record value, got {:?}", value).into()),
+        }
+    }
+
+    fn flush(&mut self) -> Result<RecordBatch, ArrowError> {
+        let array = self.builder.finish();
+        let struct_array = array
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(|| ArrowError::SchemaError("record schema did not build a struct array".to_string()))?;
+
+        let batch = RecordBatch::try_new(self.arrow_schema.clone(), struct_array.columns().to_vec())?;
+
+        self.builder = create_builder_projected(&Schema::Record(self.schema.clone()), self.batch_size, self.projection.as_ref())
+            .map_err(|e| ArrowError::SchemaError(e.to_string()))?;
+
+        Ok(batch)
+    }
+}
+
+impl<I> Iterator for RecordBatchReader<I>
+where
+    I: Iterator<Item = Result<Value, apache_avro::Error>>,
+{
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            match self.values.next() {
+                Some(Ok(value)) => {
+                    if let Err(e) = self.append(&value) {
+                        self.done = true;
+                        return Some(Err(ArrowError::ComputeError(e.to_string())));
+                    }
+                    if self.builder.len() >= self.batch_size {
+                        return Some(self.flush());
+                    }
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(ArrowError::ExternalError(Box::new(e))));
+                }
+                None => {
+                    self.done = true;
+                    return if self.builder.len() == 0 {
+                        None
+                    } else {
+                        Some(self.flush())
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<I> ArrowRecordBatchReader for RecordBatchReader<I>
+where
+    I: Iterator<Item = Result<Value, apache_avro::Error>>,
+{
+    fn schema(&self) -> SchemaRef {
+        self.arrow_schema.clone()
+    }
+}
@@ -1,13 +1,19 @@
 use std::any::Any;
+use std::collections::HashMap;
 use std::error::Error;
 
-use apache_avro::{Schema, Decimal, Uuid};
+use apache_avro::{Schema, Decimal, Duration, Uuid};
+use apache_avro::schema::RecordField;
 use apache_avro::types::Value;
 use arrow::array::builder::*;
-use arrow::datatypes::Fields;
-use num_bigint::BigInt;
+use arrow::datatypes::{Fields, IntervalMonthDayNanoType, i256};
+use num_bigint::{BigInt, Sign};
 use num_traits::ToPrimitive;
 
+use crate::dictionary::EnumDictionaryBuilder;
+use crate::schema::{is_optional, non_null_variants, Projection, MAX_DECIMAL128_PRECISION, MAX_DECIMAL256_PRECISION};
+use crate::union::DenseUnionBuilder;
+
 macro_rules! unexpected_type {
     ($name:expr, $value:expr) => {
         panic!("expected {} but got {:?}", $name, $value)
@@ -62,13 +68,57 @@ pub fn append_record(
             convert!(builder, Double, Float64Builder, record, deref)
         }
 
-        Schema::Decimal(_) => convert!(
-            builder,
-            Decimal,
-            Decimal128Builder,
-            record,
-            from_decimal128
-        ),
+        Schema::Decimal(inner) if inner.precision > MAX_DECIMAL256_PRECISION => {
+            match inner.inner.as_ref() {
+                Schema::Fixed(fixed) => {
+                    let typed = cast!(builder, FixedSizeBinaryBuilder);
+                    match record {
+                        Value::Null => Ok(typed.append_null()),
+                        Value::Decimal(v) => {
+                            validate_decimal_precision(inner.precision, v)?;
+                            Ok(typed.append_value(fixed_decimal_bytes(v, fixed.size)?)?)
+                        }
+                        _ => unexpected_type!("Decimal", record),
+                    }
+                }
+                _ => {
+                    let typed = cast!(builder, BinaryBuilder);
+                    match record {
+                        Value::Null => Ok(typed.append_null()),
+                        Value::Decimal(v) => Ok(typed.append_value(decimal_to_bigint(v).to_signed_bytes_be())),
+                        _ => unexpected_type!("Decimal", record),
+                    }
+                }
+            }
+        }
+
+        Schema::Decimal(inner) if inner.precision > MAX_DECIMAL128_PRECISION => {
+            let typed = cast!(builder, Decimal256Builder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Decimal(v) => {
+                    if matches!(inner.inner.as_ref(), Schema::Fixed(_)) {
+                        validate_decimal_precision(inner.precision, v)?;
+                    }
+                    Ok(typed.append_value(from_decimal256(v)?))
+                }
+                _ => unexpected_type!("Decimal", record),
+            }
+        }
+
+        Schema::Decimal(inner) => {
+            let typed = cast!(builder, Decimal128Builder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Decimal(v) => {
+                    if matches!(inner.inner.as_ref(), Schema::Fixed(_)) {
+                        validate_decimal_precision(inner.precision, v)?;
+                    }
+                    Ok(typed.append_value(from_decimal128(v)?))
+                }
+                _ => unexpected_type!("Decimal", record),
+            }
+        }
 
         Schema::Date => convert!(
             builder,
@@ -150,13 +200,14 @@ pub fn append_record(
             asis
         ),
 
-        Schema::Enum(_) => convert_ex!(
-            builder,
-            Enum,
-            StringBuilder,
-            record,
-            Value::Enum(_, v) => v
-        ),
+        Schema::Enum(_) => {
+            let typed = cast!(builder, EnumDictionaryBuilder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Enum(idx, _) => Ok(typed.append_index(*idx as i32)),
+                _ => unexpected_type!("Enum", record),
+            }
+        }
 
         Schema::Bytes => convert!(
             builder,
@@ -184,6 +235,15 @@ pub fn append_record(
             }
         }
 
+        Schema::Duration => {
+            let typed = cast!(builder, IntervalMonthDayNanoBuilder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Duration(v) => Ok(typed.append_value(from_duration(v))),
+                _ => unexpected_type!("Duration", record),
+            }
+        }
+
         Schema::Array(inner) => {
             let typed = cast!(builder, ListBuilder<Box<dyn ArrayBuilder>>);
             match record {
@@ -228,7 +288,7 @@ pub fn append_record(
             }
         }
 
-        Schema::Union(inner) => {
+        Schema::Union(inner) if is_optional(inner) => {
             let type_schema = &inner.variants()[1];
             match record {
                 Value::Union(_, value) => {
@@ -238,10 +298,363 @@ pub fn append_record(
             }
         }
 
+        Schema::Union(inner) => {
+            let typed = cast!(builder, DenseUnionBuilder);
+            match record {
+                Value::Union(idx, value) => {
+                    let variant = &inner.variants()[*idx as usize];
+                    if matches!(variant, Schema::Null) {
+                        // The null branch has no Arrow child of its own (see
+                        // `non_null_variants`): route the null through the
+                        // first real branch's child instead.
+                        let (fallback_id, fallback_schema) = non_null_variants(inner)
+                            .into_iter()
+                            .next()
+                            .ok_or("union has no non-null branch to hold a null value")?;
+                        let child = typed.append(fallback_id);
+                        append_record(child, fallback_schema, &Value::Null)
+                    } else {
+                        let child = typed.append(*idx as i8);
+                        append_record(child, variant, value)
+                    }
+                }
+                _ => unexpected_type!("Union", record),
+            }
+        }
+
         _ => Err(format!("unsupported schema {:?}", schema).into()),
     }
 }
 
+/// Appends `record`, written with `writer`, into `builder` built for `reader`.
+///
+/// Unlike [`append_record`], this does not assume the writer and reader
+/// schemas are identical: record fields are matched by name (reader fields
+/// missing from the writer fall back to their declared `default`, writer
+/// fields absent from the reader are skipped), and Avro's numeric promotions
+/// (int->long/float/double, long->float/double, float->double, string<->bytes)
+/// are applied where the two schemas disagree on the concrete type. An enum
+/// symbol unknown to the reader falls back to the reader's `default` symbol,
+/// and a writer value resolving into a reader union picks the first reader
+/// branch the writer's schema can promote to.
+pub fn append_record_resolved(
+    builder: &mut dyn ArrayBuilder,
+    writer: &Schema,
+    reader: &Schema,
+    record: &Value,
+) -> Result<(), Box<dyn Error>> {
+    match (writer, reader) {
+        (Schema::Record(w), Schema::Record(r)) => {
+            let typed = cast!(builder, StructBuilder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Record(fields) => {
+                    let writer_values: HashMap<&str, &Value> = w
+                        .fields
+                        .iter()
+                        .zip(fields.iter())
+                        .map(|(f, (_, v))| (f.name.as_str(), v))
+                        .collect();
+
+                    for (i, rf) in r.fields.iter().enumerate() {
+                        let target = struct_field(typed, i);
+                        match find_writer_field(&w.fields, rf) {
+                            Some(wf) => {
+                                let value = writer_values[wf.name.as_str()];
+                                append_record_resolved(target, &wf.schema, &rf.schema, value)?;
+                            }
+                            None => {
+                                let default = rf.default.as_ref().ok_or_else(|| {
+                                    format!("writer schema has no field '{}' and reader has no default", rf.name)
+                                })?;
+                                let value = default_to_value(&rf.schema, default)?;
+                                append_record(target, &rf.schema, &value)?;
+                            }
+                        }
+                    }
+
+                    Ok(typed.append(true))
+                }
+                _ => unexpected_type!("Record", record),
+            }
+        }
+
+        (Schema::Union(w), _) => match record {
+            Value::Union(idx, inner) => {
+                let branch = &w.variants()[*idx as usize];
+                append_record_resolved(builder, branch, reader, inner)
+            }
+            _ => unexpected_type!("Union", record),
+        },
+
+        (_, Schema::Union(r)) if is_optional(r) => match record {
+            Value::Null => append_record(builder, &r.variants()[1], &Value::Null),
+            _ => append_record_resolved(builder, writer, &r.variants()[1], record),
+        },
+
+        (_, Schema::Union(r)) => {
+            let typed = cast!(builder, DenseUnionBuilder);
+            let (type_id, branch) = non_null_variants(r)
+                .into_iter()
+                .find(|(_, variant)| is_promotable(writer, variant))
+                .ok_or_else(|| format!("writer schema {:?} has no compatible branch in the reader union", writer))?;
+            let child = typed.append(type_id);
+            append_record_resolved(child, writer, branch, record)
+        }
+
+        (Schema::Enum(_), Schema::Enum(r)) => {
+            let typed = cast!(builder, EnumDictionaryBuilder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Enum(_, symbol) => match r.symbols.iter().position(|s| s == symbol) {
+                    Some(idx) => Ok(typed.append_index(idx as i32)),
+                    None => {
+                        let default = r.default.as_ref().ok_or_else(|| {
+                            format!("writer enum symbol '{}' is unknown to the reader and it has no default", symbol)
+                        })?;
+                        let idx = r
+                            .symbols
+                            .iter()
+                            .position(|s| s == default)
+                            .ok_or("reader default enum symbol not found among its own symbols")?;
+                        Ok(typed.append_index(idx as i32))
+                    }
+                },
+                _ => unexpected_type!("Enum", record),
+            }
+        }
+
+        (Schema::Int, Schema::Long) => convert!(builder, Int, Int64Builder, record, |v: &i32| *v as i64),
+        (Schema::Int, Schema::Float) => convert!(builder, Int, Float32Builder, record, |v: &i32| *v as f32),
+        (Schema::Int, Schema::Double) => convert!(builder, Int, Float64Builder, record, |v: &i32| *v as f64),
+        (Schema::Long, Schema::Float) => convert!(builder, Long, Float32Builder, record, |v: &i64| *v as f32),
+        (Schema::Long, Schema::Double) => convert!(builder, Long, Float64Builder, record, |v: &i64| *v as f64),
+        (Schema::Float, Schema::Double) => convert!(builder, Float, Float64Builder, record, |v: &f32| *v as f64),
+
+        (Schema::String, Schema::Bytes) => {
+            convert!(builder, String, BinaryBuilder, record, |v: &String| v.as_bytes())
+        }
+        (Schema::Bytes, Schema::String) => {
+            convert!(builder, Bytes, StringBuilder, record, |v: &Vec<u8>| String::from_utf8_lossy(v))
+        }
+
+        _ => append_record(builder, reader, record),
+    }
+}
+
+/// Appends `record` into `builder`, but only materializes the top-level
+/// fields named in `projection` (or every field, if `projection` is `None`).
+/// Fields outside the projection are still fully present in `record` -- they
+/// were already decoded off the Avro byte stream by the caller -- they are
+/// simply never pushed into an Arrow builder, matching how `builder` itself
+/// was shaped by [`crate::schema::create_builder_projected`].
+pub fn append_record_projected(
+    builder: &mut dyn ArrayBuilder,
+    schema: &Schema,
+    record: &Value,
+    projection: Option<&Projection>,
+) -> Result<(), Box<dyn Error>> {
+    match (schema, projection) {
+        (Schema::Record(inner), Some(projection)) => {
+            let typed = cast!(builder, StructBuilder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Record(fields) => {
+                    let values: HashMap<&str, &Value> = fields
+                        .iter()
+                        .map(|(name, v)| (name.as_str(), v))
+                        .collect();
+
+                    for (i, name) in projection.names().iter().enumerate() {
+                        let f_schema = &inner
+                            .fields
+                            .iter()
+                            .find(|f| &f.name == name)
+                            .ok_or_else(|| format!("projected field '{}' not found in schema", name))?
+                            .schema;
+                        let value = values
+                            .get(name.as_str())
+                            .ok_or_else(|| format!("projected field '{}' missing from record", name))?;
+                        append_record(struct_field(typed, i), f_schema, value)?;
+                    }
+
+                    Ok(typed.append(true))
+                }
+                _ => unexpected_type!("Record", record),
+            }
+        }
+        _ => append_record(builder, schema, record),
+    }
+}
+
+/// Combines [`append_record_resolved`] and [`append_record_projected`]:
+/// resolves `writer` against `reader` field by field, as
+/// [`append_record_resolved`] does, but only materializes the top-level
+/// fields named in `projection` (or every field, if `projection` is `None`).
+pub fn append_record_resolved_projected(
+    builder: &mut dyn ArrayBuilder,
+    writer: &Schema,
+    reader: &Schema,
+    record: &Value,
+    projection: Option<&Projection>,
+) -> Result<(), Box<dyn Error>> {
+    match (writer, reader, projection) {
+        (Schema::Record(w), Schema::Record(r), Some(projection)) => {
+            let typed = cast!(builder, StructBuilder);
+            match record {
+                Value::Null => Ok(typed.append_null()),
+                Value::Record(fields) => {
+                    let writer_values: HashMap<&str, &Value> = w
+                        .fields
+                        .iter()
+                        .zip(fields.iter())
+                        .map(|(f, (_, v))| (f.name.as_str(), v))
+                        .collect();
+
+                    for (i, name) in projection.names().iter().enumerate() {
+                        let rf = r
+                            .fields
+                            .iter()
+                            .find(|f| &f.name == name)
+                            .ok_or_else(|| format!("projected field '{}' not found in schema", name))?;
+                        let target = struct_field(typed, i);
+                        match find_writer_field(&w.fields, rf) {
+                            Some(wf) => {
+                                let value = writer_values[wf.name.as_str()];
+                                append_record_resolved(target, &wf.schema, &rf.schema, value)?;
+                            }
+                            None => {
+                                let default = rf.default.as_ref().ok_or_else(|| {
+                                    format!("writer schema has no field '{}' and reader has no default", rf.name)
+                                })?;
+                                let value = default_to_value(&rf.schema, default)?;
+                                append_record(target, &rf.schema, &value)?;
+                            }
+                        }
+                    }
+
+                    Ok(typed.append(true))
+                }
+                _ => unexpected_type!("Record", record),
+            }
+        }
+        _ => append_record_resolved(builder, writer, reader, record),
+    }
+}
+
+/// Appends `value` into the struct builder's `i`-th child field, for
+/// callers (such as `reader::RecordBatchReader`) that iterate a record's
+/// fields themselves and need access to the one field builder at a time,
+/// without reaching into `StructBuilder`'s private layout directly.
+pub(crate) fn append_struct_field(
+    builder: &mut StructBuilder,
+    i: usize,
+    schema: &Schema,
+    value: &Value,
+) -> Result<(), Box<dyn Error>> {
+    append_record(struct_field(builder, i), schema, value)
+}
+
+/// Commits the row started by a run of [`append_struct_field`] calls.
+pub(crate) fn finish_struct_row(builder: &mut StructBuilder) {
+    builder.append(true);
+}
+
+fn is_promotable(writer: &Schema, reader: &Schema) -> bool {
+    match (writer, reader) {
+        (Schema::Int, Schema::Long) | (Schema::Int, Schema::Float) | (Schema::Int, Schema::Double) => true,
+        (Schema::Long, Schema::Float) | (Schema::Long, Schema::Double) => true,
+        (Schema::Float, Schema::Double) => true,
+        (Schema::String, Schema::Bytes) | (Schema::Bytes, Schema::String) => true,
+        // Named schemas only resolve to a same-named (and, for Fixed,
+        // same-sized) counterpart -- matching Avro's own resolution rule --
+        // so two unrelated records/enums/fixeds of the same Avro kind don't
+        // get silently treated as interchangeable.
+        (Schema::Record(w), Schema::Record(r)) => w.name == r.name,
+        (Schema::Enum(w), Schema::Enum(r)) => w.name == r.name,
+        (Schema::Fixed(w), Schema::Fixed(r)) => w.name == r.name && w.size == r.size,
+        (w, r) => std::mem::discriminant(w) == std::mem::discriminant(r),
+    }
+}
+
+fn find_writer_field<'a>(writer_fields: &'a [RecordField], reader_field: &RecordField) -> Option<&'a RecordField> {
+    writer_fields.iter().find(|wf| {
+        wf.name == reader_field.name
+            || reader_field
+                .aliases
+                .as_ref()
+                .is_some_and(|aliases| aliases.contains(&wf.name))
+    })
+}
+
+fn default_to_value(schema: &Schema, default: &serde_json::Value) -> Result<Value, Box<dyn Error>> {
+    match schema {
+        Schema::Null => Ok(Value::Null),
+        Schema::Boolean => Ok(Value::Boolean(
+            default.as_bool().ok_or("invalid boolean default")?,
+        )),
+        Schema::Int => Ok(Value::Int(default.as_i64().ok_or("invalid int default")? as i32)),
+        Schema::Long => Ok(Value::Long(default.as_i64().ok_or("invalid long default")?)),
+        Schema::Float => Ok(Value::Float(
+            default.as_f64().ok_or("invalid float default")? as f32,
+        )),
+        Schema::Double => Ok(Value::Double(
+            default.as_f64().ok_or("invalid double default")?,
+        )),
+        Schema::String => Ok(Value::String(
+            default.as_str().ok_or("invalid string default")?.to_string(),
+        )),
+        Schema::Bytes => Ok(Value::Bytes(
+            default.as_str().ok_or("invalid bytes default")?.as_bytes().to_vec(),
+        )),
+        Schema::Enum(e) => {
+            let symbol = default.as_str().ok_or("invalid enum default")?;
+            let idx = e
+                .symbols
+                .iter()
+                .position(|s| s == symbol)
+                .ok_or("unknown enum default symbol")?;
+            Ok(Value::Enum(idx as u32, symbol.to_string()))
+        }
+        Schema::Array(inner) => {
+            let items = default.as_array().ok_or("invalid array default")?;
+            let values: Result<Vec<Value>, Box<dyn Error>> = items
+                .iter()
+                .map(|i| default_to_value(&inner.items, i))
+                .collect();
+            Ok(Value::Array(values?))
+        }
+        Schema::Map(inner) => {
+            let obj = default.as_object().ok_or("invalid map default")?;
+            let mut map = std::collections::HashMap::new();
+            for (k, v) in obj {
+                map.insert(k.clone(), default_to_value(&inner.types, v)?);
+            }
+            Ok(Value::Map(map))
+        }
+        Schema::Union(u) => Ok(Value::Union(
+            0,
+            Box::new(default_to_value(&u.variants()[0], default)?),
+        )),
+        Schema::Record(r) => {
+            let obj = default.as_object().ok_or("invalid record default")?;
+            let mut fields = Vec::new();
+            for f in &r.fields {
+                let value = match obj.get(&f.name) {
+                    Some(v) => default_to_value(&f.schema, v)?,
+                    None => default_to_value(
+                        &f.schema,
+                        f.default.as_ref().ok_or("missing nested default")?,
+                    )?,
+                };
+                fields.push((f.name.clone(), value));
+            }
+            Ok(Value::Record(fields))
+        }
+        _ => Err(format!("unsupported default for schema {:?}", schema).into()),
+    }
+}
+
 #[inline(always)]
 pub fn cast_unchecked<T>(any: &mut dyn Any) -> &mut T {
     unsafe { &mut *(any as *mut dyn Any as *mut T) }
@@ -275,6 +688,38 @@ fn decimal_to_bigint(d: &Decimal) -> &BigInt {
     }
 }
 
+#[inline(always)]
+fn decimal_len(d: &Decimal) -> usize {
+    unsafe {
+        let shadow = d as *const dyn Any as *const DecimalLayout;
+        (*shadow).len
+    }
+}
+
+/// The largest decimal precision representable in a big-endian two's
+/// complement byte array of length `len`, mirroring Avro's own
+/// `max_prec_for_len` check performed when a decimal is written.
+#[inline(always)]
+fn max_prec_for_len(len: usize) -> u64 {
+    (2f64.powi(8 * len as i32 - 1) - 1.0).log10().floor() as u64
+}
+
+/// Guards against a `Fixed`-backed decimal whose declared `precision` cannot
+/// possibly fit in its constant byte length (unlike `Bytes`-backed decimals,
+/// which legitimately use a minimal, value-dependent encoding length, a
+/// `Fixed` size is fixed by the schema and bounds every value it carries).
+fn validate_decimal_precision(precision: usize, d: &Decimal) -> Result<(), Box<dyn Error>> {
+    let len = decimal_len(d);
+    if precision as u64 > max_prec_for_len(len) {
+        return Err(format!(
+            "decimal value encoded in {} byte(s) cannot hold the declared precision {}",
+            len, precision,
+        )
+        .into());
+    }
+    Ok(())
+}
+
 #[inline(always)]
 fn asis<T>(v: &T) -> &T {
     v
@@ -289,17 +734,51 @@ where
 }
 
 #[inline(always)]
-fn from_decimal128(d: &Decimal) -> i128 {
-    match decimal_to_bigint(d).to_i128() {
-        Some(v) => v,
-        None => {
-            // TODO: Log warn
-            0
-        }
+fn from_decimal128(d: &Decimal) -> Result<i128, Box<dyn Error>> {
+    decimal_to_bigint(d)
+        .to_i128()
+        .ok_or_else(|| "decimal value does not fit in 128 bits".into())
+}
+
+#[inline(always)]
+fn from_decimal256(d: &Decimal) -> Result<i256, Box<dyn Error>> {
+    let big = decimal_to_bigint(d);
+    let bytes = big.to_signed_bytes_le();
+    if bytes.len() > 32 {
+        return Err("decimal value does not fit in 256 bits".into());
     }
+
+    let fill = if big.sign() == Sign::Minus { 0xffu8 } else { 0u8 };
+    let mut buf = [fill; 32];
+    buf[..bytes.len()].copy_from_slice(&bytes);
+
+    Ok(i256::from_le_bytes(buf))
+}
+
+#[inline(always)]
+fn fixed_decimal_bytes(d: &Decimal, size: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+    let big = decimal_to_bigint(d);
+    let bytes = big.to_signed_bytes_be();
+    if bytes.len() > size {
+        return Err(format!("decimal value does not fit in {} bytes", size).into());
+    }
+
+    let fill = if big.sign() == Sign::Minus { 0xffu8 } else { 0u8 };
+    let mut buf = vec![fill; size];
+    buf[size - bytes.len()..].copy_from_slice(&bytes);
+
+    Ok(buf)
 }
 
 #[inline(always)]
 fn from_uuid(u: &Uuid) -> &[u8; 16] {
     u.as_bytes()
 }
+
+#[inline(always)]
+fn from_duration(d: &Duration) -> i128 {
+    let months: u32 = d.months().into();
+    let days: u32 = d.days().into();
+    let millis: u32 = d.millis().into();
+    IntervalMonthDayNanoType::make_value(months as i32, days as i32, millis as i64 * 1_000_000)
+}
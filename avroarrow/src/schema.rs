@@ -2,10 +2,14 @@ use std::error::Error;
 use std::sync::Arc;
 
 use apache_avro::Schema as AvroSchema;
-use apache_avro::schema::{ArraySchema, DecimalSchema, MapSchema, RecordSchema, UnionSchema};
+use apache_avro::schema::{ArraySchema, DecimalSchema, MapSchema, RecordField, RecordSchema, UnionSchema};
 use arrow::datatypes::{Schema as ArrowSchema, *};
 use arrow::array::builder::*;
 use chrono::Local;
+use serde_json::{json, Value};
+
+use crate::dictionary::EnumDictionaryBuilder;
+use crate::union::DenseUnionBuilder;
 
 macro_rules! tz_offset {
     () => {
@@ -13,12 +17,63 @@ macro_rules! tz_offset {
     };
 }
 
+/// A set of top-level record field names to keep when converting a schema
+/// or building its Arrow arrays; fields outside the projection are fully
+/// decoded off the Avro value but never reach an Arrow `Field`/`ArrayBuilder`.
+/// Columns in the resulting schema/`RecordBatch` follow projection order
+/// rather than the order fields appear in the Avro schema.
+#[derive(Debug, Clone)]
+pub struct Projection {
+    fields: Vec<String>,
+}
+
+impl Projection {
+    pub fn new<I, S>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            fields: fields.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.fields
+    }
+}
+
+fn select_fields<'a>(
+    fields: &'a [RecordField],
+    projection: Option<&Projection>,
+) -> Result<Vec<&'a RecordField>, Box<dyn Error>> {
+    match projection {
+        None => Ok(fields.iter().collect()),
+        Some(projection) => projection
+            .names()
+            .iter()
+            .map(|name| {
+                fields
+                    .iter()
+                    .find(|f| &f.name == name)
+                    .ok_or_else(|| format!("projected field '{}' not found in schema", name).into())
+            })
+            .collect(),
+    }
+}
+
 pub fn convert_schema(src: &AvroSchema) -> Result<ArrowSchema, Box<dyn Error>> {
+    convert_schema_projected(src, None)
+}
+
+pub fn convert_schema_projected(
+    src: &AvroSchema,
+    projection: Option<&Projection>,
+) -> Result<ArrowSchema, Box<dyn Error>> {
     match src {
         AvroSchema::Record(record_schema) => {
-            let arrow_fields: Result<Vec<Field>, Box<dyn Error>> = record_schema
-                .fields
-                .iter()
+            let arrow_fields: Result<Vec<Field>, Box<dyn Error>> = select_fields(&record_schema.fields, projection)?
+                .into_iter()
                 .map(|field| Ok(Field::new(
                     field.name.clone(),
                     convert_to_datatype(&field.schema)?,
@@ -63,11 +118,10 @@ fn convert_to_datatype(src: &AvroSchema) -> Result<DataType, Box<dyn Error>> {
             Ok(DataType::Timestamp(TimeUnit::Nanosecond, Some(tz_offset!())))
         }
         AvroSchema::Date => Ok(DataType::Date32),
-        AvroSchema::Enum { .. } => Ok(DataType::Utf8),
-        AvroSchema::Decimal(schema) => {
-            Ok(DataType::Decimal128(schema.precision as u8, schema.scale as i8))
-        }
+        AvroSchema::Enum { .. } => Ok(DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))),
+        AvroSchema::Decimal(schema) => Ok(arrow_decimal_type(schema)),
         AvroSchema::Fixed(schema) => Ok(DataType::FixedSizeBinary(schema.size as i32)),
+        AvroSchema::Duration => Ok(DataType::Interval(IntervalUnit::MonthDayNano)),
         AvroSchema::BigDecimal => Ok(DataType::Binary),
         AvroSchema::Array(schema) => {
             Ok(DataType::List(Arc::new(Field::new(
@@ -95,7 +149,7 @@ fn convert_to_datatype(src: &AvroSchema) -> Result<DataType, Box<dyn Error>> {
             if is_optional(schema) {
                 convert_to_datatype(&schema.variants()[1])
             } else {
-                Err("only unions of king [null, TYPE] are supported".into())
+                Ok(DataType::Union(union_fields(schema)?, UnionMode::Dense))
             }
         }
         AvroSchema::Record(schema) => {
@@ -126,13 +180,188 @@ fn is_nullable(src: &AvroSchema) -> bool {
     }
 }
 
-fn is_optional(src: &UnionSchema) -> bool {
+pub(crate) fn is_optional(src: &UnionSchema) -> bool {
     src.variants().len() == 2 && matches!(src.variants()[0], AvroSchema::Null)
 }
 
+/// Returns the union's non-null branches, keyed by their stable index in
+/// `schema.variants()` so an Arrow union type id always identifies the
+/// same Avro variant regardless of where a leading `null` branch sits.
+///
+/// A `null` branch never becomes a union child: it is folded into the
+/// enclosing field's nullability (see [`is_nullable`]) instead, matching
+/// how a `[null, T]` union already collapses to a plain nullable `T`.
+pub(crate) fn non_null_variants(schema: &UnionSchema) -> Vec<(i8, &AvroSchema)> {
+    schema
+        .variants()
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| !matches!(v, AvroSchema::Null))
+        .map(|(i, v)| (i as i8, v))
+        .collect()
+}
+
+fn union_fields(schema: &UnionSchema) -> Result<UnionFields, Box<dyn Error>> {
+    let mut ids: Vec<i8> = Vec::new();
+    let mut fields: Vec<Field> = Vec::new();
+
+    for (id, variant) in non_null_variants(schema) {
+        let name = format!("{}_{}", variant_type_name(variant), id);
+        fields.push(Field::new(name, convert_to_datatype(variant)?, false));
+        ids.push(id);
+    }
+
+    Ok(UnionFields::new(ids, fields))
+}
+
+fn variant_type_name(src: &AvroSchema) -> &'static str {
+    match src {
+        AvroSchema::Null => "null",
+        AvroSchema::Boolean => "boolean",
+        AvroSchema::Int => "int",
+        AvroSchema::Long => "long",
+        AvroSchema::Float => "float",
+        AvroSchema::Double => "double",
+        AvroSchema::Bytes => "bytes",
+        AvroSchema::String => "string",
+        AvroSchema::Array(_) => "array",
+        AvroSchema::Map(_) => "map",
+        AvroSchema::Record(_) => "record",
+        AvroSchema::Enum(_) => "enum",
+        AvroSchema::Fixed(_) => "fixed",
+        _ => "variant",
+    }
+}
+
+pub fn convert_arrow_schema(src: &ArrowSchema) -> Result<AvroSchema, Box<dyn Error>> {
+    let fields: Result<Vec<Value>, Box<dyn Error>> = src
+        .fields()
+        .iter()
+        .map(|field| convert_arrow_field(field))
+        .collect();
+
+    let record = json!({
+        "type": "record",
+        "name": "Envelope",
+        "fields": fields?,
+    });
+
+    Ok(AvroSchema::parse(&record)?)
+}
+
+fn convert_arrow_field(field: &Field) -> Result<Value, Box<dyn Error>> {
+    let inner = convert_arrow_datatype(field.name(), field.data_type())?;
+    let field_type = if field.is_nullable() {
+        json!(["null", inner])
+    } else {
+        inner
+    };
+
+    Ok(json!({
+        "name": field.name(),
+        "type": field_type,
+    }))
+}
+
+fn convert_arrow_datatype(name: &str, src: &DataType) -> Result<Value, Box<dyn Error>> {
+    match src {
+        DataType::Null => Ok(json!("null")),
+        DataType::Boolean => Ok(json!("boolean")),
+        DataType::Int32 => Ok(json!("int")),
+        DataType::Int64 => Ok(json!("long")),
+        DataType::Float32 => Ok(json!("float")),
+        DataType::Float64 => Ok(json!("double")),
+        DataType::Utf8 => Ok(json!("string")),
+        DataType::Binary => Ok(json!("bytes")),
+        DataType::FixedSizeBinary(16) => Ok(json!({
+            "type": "string",
+            "logicalType": "uuid",
+        })),
+        DataType::FixedSizeBinary(size) => Ok(json!({
+            "type": "fixed",
+            "name": format!("{}_fixed", name),
+            "size": size,
+        })),
+        DataType::Date32 => Ok(json!({
+            "type": "int",
+            "logicalType": "date",
+        })),
+        DataType::Time32(TimeUnit::Millisecond) => Ok(json!({
+            "type": "int",
+            "logicalType": "time-millis",
+        })),
+        DataType::Time64(TimeUnit::Microsecond) => Ok(json!({
+            "type": "long",
+            "logicalType": "time-micros",
+        })),
+        DataType::Timestamp(unit, tz) => {
+            let local = tz.is_some();
+            let logical = match (unit, local) {
+                (TimeUnit::Millisecond, false) => "timestamp-millis",
+                (TimeUnit::Microsecond, false) => "timestamp-micros",
+                (TimeUnit::Nanosecond, false) => "timestamp-nanos",
+                (TimeUnit::Millisecond, true) => "local-timestamp-millis",
+                (TimeUnit::Microsecond, true) => "local-timestamp-micros",
+                (TimeUnit::Nanosecond, true) => "local-timestamp-nanos",
+                (TimeUnit::Second, _) => return Err("avro has no second-precision timestamp".into()),
+            };
+            Ok(json!({
+                "type": "long",
+                "logicalType": logical,
+            }))
+        }
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => Ok(json!({
+            "type": "bytes",
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale,
+        })),
+        DataType::List(item) => Ok(json!({
+            "type": "array",
+            "items": convert_arrow_datatype(item.name(), item.data_type())?,
+        })),
+        // `convert_schema` maps an Avro enum to `Dictionary(Int32, Utf8)` --
+        // keyed the same way `convert_to_datatype`'s `Enum` arm builds it --
+        // which otherwise round-trips back to the generic string-keyed `map`
+        // a `Dictionary(Utf8, _)` represents. But a bare `DataType` carries
+        // no symbol list, and an `enum` schema with no symbols can't encode
+        // or decode a single value, so there is no valid schema to emit here:
+        // fail instead of returning one that looks valid but isn't usable.
+        DataType::Dictionary(key, value) if key.as_ref() == &DataType::Int32 && value.as_ref() == &DataType::Utf8 => {
+            Err("cannot derive an avro enum schema for a Dictionary(Int32, Utf8) column: \
+                 its symbol list isn't recoverable from the arrow data type alone".into())
+        }
+        DataType::Dictionary(_, value) => Ok(json!({
+            "type": "map",
+            "values": convert_arrow_datatype(name, value)?,
+        })),
+        DataType::Struct(fields) => {
+            let avro_fields: Result<Vec<Value>, Box<dyn Error>> = fields
+                .iter()
+                .map(|f| convert_arrow_field(f))
+                .collect();
+
+            Ok(json!({
+                "type": "record",
+                "name": format!("{}_record", name),
+                "fields": avro_fields?,
+            }))
+        }
+        _ => Err(format!("unsupported arrow data type {:?}", src).into()),
+    }
+}
+
 pub fn create_builder(
     avro: &AvroSchema,
     cap: usize,
+) -> Result<Box<dyn ArrayBuilder>, Box<dyn Error>> {
+    create_builder_projected(avro, cap, None)
+}
+
+pub fn create_builder_projected(
+    avro: &AvroSchema,
+    cap: usize,
+    projection: Option<&Projection>,
 ) -> Result<Box<dyn ArrayBuilder>, Box<dyn Error>> {
     match avro {
         AvroSchema::Null => Ok(Box::new(NullBuilder::new())),
@@ -141,7 +370,7 @@ pub fn create_builder(
         AvroSchema::Long => Ok(Box::new(Int64Builder::with_capacity(cap))),
         AvroSchema::Float => Ok(Box::new(Float32Builder::with_capacity(cap))),
         AvroSchema::Double => Ok(Box::new(Float64Builder::with_capacity(cap))),
-        AvroSchema::Decimal(schema) => Ok(Box::new(decimal_builder(schema, cap))),
+        AvroSchema::Decimal(schema) => Ok(decimal_builder(schema, cap)),
         AvroSchema::Date => Ok(Box::new(Date32Builder::with_capacity(cap))),
         AvroSchema::TimeMillis => Ok(Box::new(Time32MillisecondBuilder::with_capacity(cap))),
         AvroSchema::TimeMicros => Ok(Box::new(Time64MicrosecondBuilder::with_capacity(cap))),
@@ -153,31 +382,70 @@ pub fn create_builder(
         AvroSchema::LocalTimestampNanos => Ok(Box::new(local_timestamp_ns_builder(cap))),
         AvroSchema::Bytes => Ok(Box::new(BinaryBuilder::with_capacity(cap, 2048))),
         AvroSchema::Fixed(schema) => Ok(Box::new(FixedSizeBinaryBuilder::with_capacity(cap, schema.size as i32))),
+        AvroSchema::Duration => Ok(Box::new(IntervalMonthDayNanoBuilder::with_capacity(cap))),
         AvroSchema::String => Ok(Box::new(StringBuilder::with_capacity(cap, 2048))),
-        AvroSchema::Enum(_) => Ok(Box::new(StringBuilder::with_capacity(cap, 2048))),
+        AvroSchema::Enum(schema) => Ok(Box::new(EnumDictionaryBuilder::new(&schema.symbols, cap))),
         AvroSchema::Uuid => Ok(Box::new(FixedSizeBinaryBuilder::with_capacity(cap, 16))),
         AvroSchema::Array(schema) => array_builder(schema, cap),
         AvroSchema::Map(schema) => map_builder(schema, cap),
-        AvroSchema::Record(schema) => struct_builder(schema, cap),
+        AvroSchema::Record(schema) => struct_builder(schema, cap, projection),
         AvroSchema::Union(schema) => {
             if is_optional(schema) {
-                create_builder(&schema.variants()[1], cap)
+                create_builder_projected(&schema.variants()[1], cap, projection)
             } else {
-                Err("only unions of king [null, TYPE] are supported".into())
+                let fields = union_fields(schema)?;
+                let children: Result<Vec<Box<dyn ArrayBuilder>>, Box<dyn Error>> = non_null_variants(schema)
+                    .into_iter()
+                    .map(|(_, variant)| create_builder(variant, cap))
+                    .collect();
+                Ok(Box::new(DenseUnionBuilder::new(fields, children?)))
             }
         }
         _ => Err(format!("cannot create builder for {:?}", avro).into()),
     }
 }
 
+/// Avro decimals wider than 38 digits of precision overflow `Decimal128` and
+/// need the 256-bit representation instead.
+pub(crate) const MAX_DECIMAL128_PRECISION: usize = 38;
+
+/// Beyond 76 digits, even `Decimal256` can't hold the value: there is no
+/// literal Arrow decimal type wide enough. Following Avro's own rule of
+/// falling back to a logical type's underlying physical encoding when it
+/// can't be represented exactly, such decimals map to their plain `Bytes`/
+/// `Fixed` Arrow equivalent instead of producing an invalid `Decimal256`.
+pub(crate) const MAX_DECIMAL256_PRECISION: usize = 76;
+
 #[inline]
 fn arrow_decimal_type(schema: &DecimalSchema) -> DataType {
-    DataType::Decimal128(schema.precision as u8, schema.scale as i8)
+    if schema.precision > MAX_DECIMAL256_PRECISION {
+        fallback_decimal_type(schema)
+    } else if schema.precision > MAX_DECIMAL128_PRECISION {
+        DataType::Decimal256(schema.precision as u8, schema.scale as i8)
+    } else {
+        DataType::Decimal128(schema.precision as u8, schema.scale as i8)
+    }
+}
+
+fn fallback_decimal_type(schema: &DecimalSchema) -> DataType {
+    match schema.inner.as_ref() {
+        AvroSchema::Fixed(fixed) => DataType::FixedSizeBinary(fixed.size as i32),
+        _ => DataType::Binary,
+    }
 }
 
 #[inline]
-fn decimal_builder(schema: &DecimalSchema, cap: usize) -> Decimal128Builder {
-    Decimal128Builder::with_capacity(cap).with_data_type(arrow_decimal_type(schema))
+fn decimal_builder(schema: &DecimalSchema, cap: usize) -> Box<dyn ArrayBuilder> {
+    if schema.precision > MAX_DECIMAL256_PRECISION {
+        match schema.inner.as_ref() {
+            AvroSchema::Fixed(fixed) => Box::new(FixedSizeBinaryBuilder::with_capacity(cap, fixed.size as i32)),
+            _ => Box::new(BinaryBuilder::with_capacity(cap, 2048)),
+        }
+    } else if schema.precision > MAX_DECIMAL128_PRECISION {
+        Box::new(Decimal256Builder::with_capacity(cap).with_data_type(arrow_decimal_type(schema)))
+    } else {
+        Box::new(Decimal128Builder::with_capacity(cap).with_data_type(arrow_decimal_type(schema)))
+    }
 }
 
 #[inline]
@@ -222,11 +490,12 @@ fn map_builder(
 fn struct_builder(
     schema: &RecordSchema,
     cap: usize,
+    projection: Option<&Projection>,
 ) -> Result<Box<dyn ArrayBuilder>, Box<dyn Error>> {
     let mut fields: Vec<Field> = Vec::new();
     let mut builders: Vec<Box<dyn ArrayBuilder>> = Vec::new();
 
-    for f in &schema.fields {
+    for f in select_fields(&schema.fields, projection)? {
         let t = convert_to_datatype(&f.schema)?;
         fields.push(Field::new(f.name.clone(), t, is_nullable(&f.schema)));
         builders.push(create_builder(&f.schema, cap)?);
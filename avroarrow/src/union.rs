@@ -0,0 +1,95 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::builder::ArrayBuilder;
+use arrow::array::{ArrayRef, UnionArray};
+use arrow::datatypes::UnionFields;
+
+/// An [`ArrayBuilder`] for Arrow's dense union layout, used to build the
+/// child arrays behind a multi-branch Avro union (any union other than
+/// `[null, T]`). A `null` branch has no child of its own; a null value is
+/// stored as a null in the first non-null branch's child.
+///
+/// Each branch of the union owns one child builder, keyed by its stable
+/// Avro variant index (the "type id"). Appending a value to a branch
+/// records that branch's type id and its current child length (the dense
+/// offset) without touching any other child, keeping the offsets buffer
+/// consistent.
+pub struct DenseUnionBuilder {
+    fields: UnionFields,
+    children: Vec<Box<dyn ArrayBuilder>>,
+    type_ids: Vec<i8>,
+    offsets: Vec<i32>,
+}
+
+impl DenseUnionBuilder {
+    pub fn new(fields: UnionFields, children: Vec<Box<dyn ArrayBuilder>>) -> Self {
+        Self {
+            fields,
+            children,
+            type_ids: Vec::new(),
+            offsets: Vec::new(),
+        }
+    }
+
+    fn child_index(&self, type_id: i8) -> usize {
+        self.fields
+            .iter()
+            .position(|(id, _)| id == type_id)
+            .unwrap_or_else(|| panic!("unknown union type id {}", type_id))
+    }
+
+    /// Records that the next value belongs to `type_id` and returns the
+    /// child builder it must be appended to.
+    pub fn append(&mut self, type_id: i8) -> &mut dyn ArrayBuilder {
+        let idx = self.child_index(type_id);
+        let offset = self.children[idx].len() as i32;
+        self.type_ids.push(type_id);
+        self.offsets.push(offset);
+        self.children[idx].as_mut()
+    }
+}
+
+impl ArrayBuilder for DenseUnionBuilder {
+    fn len(&self) -> usize {
+        self.type_ids.len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        let children: Vec<ArrayRef> = self.children.iter_mut().map(|c| c.finish()).collect();
+        Arc::new(
+            UnionArray::try_new(
+                self.fields.clone(),
+                self.type_ids.clone().into(),
+                Some(self.offsets.clone().into()),
+                children,
+            )
+            .expect("inconsistent dense union buffers"),
+        )
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        let children: Vec<ArrayRef> = self.children.iter().map(|c| c.finish_cloned()).collect();
+        Arc::new(
+            UnionArray::try_new(
+                self.fields.clone(),
+                self.type_ids.clone().into(),
+                Some(self.offsets.clone().into()),
+                children,
+            )
+            .expect("inconsistent dense union buffers"),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
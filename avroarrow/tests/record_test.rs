@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
-use apache_avro::schema::{ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Schema};
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, RecordField, RecordFieldOrder,
+    RecordSchema, Schema, UnionSchema,
+};
 use apache_avro::Decimal;
 use apache_avro::AvroSchema;
 use apache_avro::types::Value;
 use arrow::array::*;
-use arrow::datatypes::{DataType, TimeUnit};
+use arrow::datatypes::{DataType, Field, Int32Type, IntervalMonthDayNanoType, TimeUnit, UnionFields, i256};
 use chrono::Local;
 use pretty_assertions::assert_eq;
 use serde::Serialize;
+use serde_json::json;
 use maplit::hashmap;
 use num_bigint::ToBigInt;
 use uuid;
@@ -153,6 +157,211 @@ fn test_append_decimal() {
     assert_eq!(*expected, *actual);
 }
 
+fn fixed_name(name: &str) -> apache_avro::schema::Name {
+    apache_avro::schema::Name {
+        name: name.to_string(),
+        namespace: None,
+    }
+}
+
+fn record_field(name: &str, schema: Schema, default: Option<serde_json::Value>) -> RecordField {
+    RecordField {
+        name: name.to_string(),
+        doc: None,
+        aliases: None,
+        default,
+        schema,
+        order: RecordFieldOrder::Ascending,
+        position: 0,
+        custom_attributes: Default::default(),
+    }
+}
+
+fn record_schema(name: &str, fields: Vec<RecordField>) -> Schema {
+    Schema::Record(RecordSchema {
+        name: fixed_name(name),
+        aliases: None,
+        doc: None,
+        lookup: fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.name.clone(), i))
+            .collect(),
+        attributes: Default::default(),
+        fields,
+    })
+}
+
+fn to_arrow_resolved<T: ArrayBuilder>(
+    writer: &Schema,
+    reader: &Schema,
+    values: &Vec<Value>,
+) -> ArrayRef {
+    let mut builder = avroarrow::create_builder(reader, 32).unwrap();
+    for v in values {
+        avroarrow::append_record_resolved(&mut builder, writer, reader, v).unwrap();
+    }
+
+    builder
+        .as_any_mut()
+        .downcast_mut::<T>()
+        .unwrap()
+        .finish()
+}
+
+fn to_signed_bytes_be_sized(v: i128, size: usize) -> Vec<u8> {
+    let big = v.to_bigint().unwrap();
+    let minimal = big.to_signed_bytes_be();
+    let fill = if v < 0 { 0xffu8 } else { 0u8 };
+    let mut buf = vec![fill; size];
+    buf[size - minimal.len()..].copy_from_slice(&minimal);
+    buf
+}
+
+#[test]
+fn test_append_decimal_fixed() {
+    let raw: Vec<i128> = vec![1024, -2048, 4096];
+
+    let schema = Schema::Decimal(DecimalSchema {
+        precision: 10,
+        scale: 4,
+        inner: Box::new(Schema::Fixed(FixedSchema {
+            name: fixed_name("dec"),
+            aliases: None,
+            doc: None,
+            size: 8,
+            default: None,
+            attributes: Default::default(),
+        })),
+    });
+
+    let values: Vec<_> = raw
+        .iter()
+        .map(|v| Value::Decimal(Decimal::from(to_signed_bytes_be_sized(*v, 8))))
+        .collect();
+
+    let expected: ArrayRef = Arc::new(Decimal128Array::from(raw).with_data_type(DataType::Decimal128(10, 4)));
+
+    let actual = to_arrow::<Decimal128Builder>(&schema, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_decimal_fixed_precision_exceeds_len() {
+    let schema = Schema::Decimal(DecimalSchema {
+        precision: 20,
+        scale: 0,
+        inner: Box::new(Schema::Fixed(FixedSchema {
+            name: fixed_name("dec"),
+            aliases: None,
+            doc: None,
+            size: 4,
+            default: None,
+            attributes: Default::default(),
+        })),
+    });
+
+    let values = vec![Value::Decimal(Decimal::from(vec![0x01, 0x02, 0x03, 0x04]))];
+
+    let mut builder = avroarrow::create_builder(&schema, 32).unwrap();
+    let err = avroarrow::append_record(&mut builder, &schema, &values[0]);
+
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_append_decimal256() {
+    let raw: Vec<i128> = vec![1024, -2048, 4096];
+
+    let schema = Schema::Decimal(DecimalSchema {
+        precision: 50,
+        scale: 4,
+        inner: Box::new(Schema::Bytes),
+    });
+
+    let values: Vec<_> = raw
+        .iter()
+        .map(|v| {
+            let b = v.to_bigint().unwrap().to_signed_bytes_be();
+            Value::Decimal(Decimal::from(b))
+        })
+        .collect();
+
+    let expected_raw: Vec<i256> = raw.iter().map(|v| i256::from_i128(*v)).collect();
+    let expected: ArrayRef = Arc::new(
+        Decimal256Array::from(expected_raw).with_data_type(DataType::Decimal256(50, 4)),
+    );
+
+    let actual = to_arrow::<Decimal256Builder>(&schema, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_decimal_bytes_value_too_large_for_128_bits_errors() {
+    let schema = Schema::Decimal(DecimalSchema {
+        precision: 20,
+        scale: 0,
+        inner: Box::new(Schema::Bytes),
+    });
+
+    // 17 bytes is too wide to fit in 128 bits, even though `precision` is
+    // well within `MAX_DECIMAL128_PRECISION`: a `Bytes`-backed decimal's
+    // encoded length isn't schema-bound the way a `Fixed`-backed one is, so
+    // this only surfaces when actually converting the value.
+    let raw = vec![0x7fu8; 17];
+    let values = vec![Value::Decimal(Decimal::from(raw))];
+
+    let mut builder = avroarrow::create_builder(&schema, 32).unwrap();
+    let err = avroarrow::append_record(&mut builder, &schema, &values[0]);
+
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_append_decimal_precision_exceeds_256_bits_falls_back_to_binary() {
+    let schema = Schema::Decimal(DecimalSchema {
+        precision: 80,
+        scale: 0,
+        inner: Box::new(Schema::Bytes),
+    });
+
+    let raw: Vec<u8> = vec![0x01, 0x02, 0x03];
+    let values = vec![Value::Decimal(Decimal::from(raw.clone()))];
+
+    let expected: ArrayRef = Arc::new(BinaryArray::from_vec(vec![&raw[..]]));
+    let actual = to_arrow::<BinaryBuilder>(&schema, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_decimal_precision_exceeds_256_bits_falls_back_to_fixed() {
+    let schema = Schema::Decimal(DecimalSchema {
+        precision: 80,
+        scale: 0,
+        inner: Box::new(Schema::Fixed(FixedSchema {
+            name: fixed_name("dec"),
+            aliases: None,
+            doc: None,
+            size: 34,
+            default: None,
+            attributes: Default::default(),
+        })),
+    });
+
+    let bytes = to_signed_bytes_be_sized(123456789, 34);
+    let values = vec![Value::Decimal(Decimal::from(bytes.clone()))];
+
+    let expected: ArrayRef = Arc::new(
+        FixedSizeBinaryArray::try_from_sparse_iter_with_size(vec![Some(&bytes)].into_iter(), 34).unwrap(),
+    );
+    let actual = to_arrow::<FixedSizeBinaryBuilder>(&schema, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
 #[test]
 fn test_append_date() {
     let raw: Vec<i32> = vec![
@@ -183,6 +392,34 @@ fn test_append_time_ms() {
     assert_eq!(*expected, *actual);
 }
 
+#[test]
+fn test_append_duration() {
+    let raw: Vec<(u32, u32, u32)> = vec![(1, 2, 3_000), (0, 10, 500), (12, 0, 0)];
+
+    let schema = Schema::Duration;
+    let values: Vec<_> = raw
+        .iter()
+        .map(|(months, days, millis)| {
+            Value::Duration(apache_avro::Duration::new(
+                apache_avro::Months::new(*months),
+                apache_avro::Days::new(*days),
+                apache_avro::Millis::new(*millis),
+            ))
+        })
+        .collect();
+
+    let expected = to_array::<i128, IntervalMonthDayNanoArray>(
+        raw.iter()
+            .map(|(months, days, millis)| {
+                IntervalMonthDayNanoType::make_value(*months as i32, *days as i32, *millis as i64 * 1_000_000)
+            })
+            .collect(),
+    );
+    let actual = to_arrow::<IntervalMonthDayNanoBuilder>(&schema, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
 #[test]
 fn test_append_time_mc() {
     let raw: Vec<i64> = vec![42000000, 52000000, 62000000];
@@ -351,10 +588,13 @@ fn test_append_enum() {
         .map(|(i, v)| Value::Enum(i as u32, v.clone()))
         .collect();
 
-    let expected = to_array::<String, StringArray>(raw);
-    let actual = to_arrow::<StringBuilder>(&schema, &values);
+    let expected_keys = Int32Array::from(vec![0, 1, 2]);
+    let expected_values: ArrayRef = Arc::new(StringArray::from(raw));
+    let expected = DictionaryArray::<Int32Type>::try_new(expected_keys, expected_values).unwrap();
 
-    assert_eq!(*expected, *actual);
+    let actual = to_arrow::<avroarrow::dictionary::EnumDictionaryBuilder>(&schema, &values);
+
+    assert_eq!(expected, *actual.as_any().downcast_ref::<DictionaryArray<Int32Type>>().unwrap());
 }
 
 #[test]
@@ -610,3 +850,304 @@ fn test_append_struct() {
         _ => {  }
     } */
 }
+
+#[test]
+fn test_append_union_multi() {
+    let schema = Schema::Union(UnionSchema::new(vec![
+        Schema::Int,
+        Schema::String,
+        Schema::Bytes,
+    ]).unwrap());
+
+    let values = vec![
+        Value::Union(0, Box::new(Value::Int(7))),
+        Value::Union(1, Box::new(Value::String("a".to_string()))),
+        Value::Union(2, Box::new(Value::Bytes(vec![1, 2, 3]))),
+        Value::Union(0, Box::new(Value::Int(9))),
+    ];
+
+    let fields = UnionFields::new(
+        vec![0, 1, 2],
+        vec![
+            Field::new("int_0", DataType::Int32, false),
+            Field::new("string_1", DataType::Utf8, false),
+            Field::new("bytes_2", DataType::Binary, false),
+        ],
+    );
+    let type_ids = vec![0i8, 1, 2, 0];
+    let offsets = vec![0i32, 0, 0, 1];
+    let children: Vec<ArrayRef> = vec![
+        Arc::new(Int32Array::from(vec![7, 9])),
+        Arc::new(StringArray::from(vec!["a"])),
+        Arc::new(BinaryArray::from(vec![&[1u8, 2, 3][..]])),
+    ];
+    let expected = UnionArray::try_new(fields, type_ids.into(), Some(offsets.into()), children).unwrap();
+
+    let actual = to_arrow::<avroarrow::union::DenseUnionBuilder>(&schema, &values);
+
+    assert_eq!(expected, *actual.as_any().downcast_ref::<UnionArray>().unwrap());
+}
+
+#[test]
+fn test_append_record_resolved_reader_adds_field_with_default() {
+    let writer = record_schema("rec", vec![record_field("a", Schema::Int, None)]);
+    let reader = record_schema(
+        "rec",
+        vec![
+            record_field("a", Schema::Int, None),
+            record_field("b", Schema::Int, Some(json!(7))),
+        ],
+    );
+
+    let values = vec![
+        Value::Record(vec![("a".to_string(), Value::Int(1))]),
+        Value::Record(vec![("a".to_string(), Value::Int(2))]),
+    ];
+
+    let mut builder = avroarrow::create_builder(&reader, 32).unwrap();
+    for v in &values {
+        avroarrow::append_record_resolved(&mut builder, &writer, &reader, v).unwrap();
+    }
+
+    let actual = builder.as_any_mut().downcast_mut::<StructBuilder>().unwrap().finish();
+
+    assert_eq!(actual.column(0).as_ref(), &Int32Array::from(vec![1, 2]));
+    assert_eq!(actual.column(1).as_ref(), &Int32Array::from(vec![7, 7]));
+}
+
+#[test]
+fn test_append_record_resolved_writer_field_dropped() {
+    let writer = record_schema(
+        "rec",
+        vec![
+            record_field("a", Schema::Int, None),
+            record_field("extra", Schema::String, None),
+        ],
+    );
+    let reader = record_schema("rec", vec![record_field("a", Schema::Int, None)]);
+
+    let values = vec![Value::Record(vec![
+        ("a".to_string(), Value::Int(1)),
+        ("extra".to_string(), Value::String("dropped".to_string())),
+    ])];
+
+    let mut builder = avroarrow::create_builder(&reader, 32).unwrap();
+    for v in &values {
+        avroarrow::append_record_resolved(&mut builder, &writer, &reader, v).unwrap();
+    }
+
+    let actual = builder.as_any_mut().downcast_mut::<StructBuilder>().unwrap().finish();
+
+    assert_eq!(actual.num_columns(), 1);
+    assert_eq!(actual.column(0).as_ref(), &Int32Array::from(vec![1]));
+}
+
+#[test]
+fn test_append_record_resolved_nullable_field_stays_nullable() {
+    let field_schema = Schema::Union(UnionSchema::new(vec![Schema::Null, Schema::String]).unwrap());
+    let schema = record_schema("rec", vec![record_field("name", field_schema, None)]);
+
+    let values = vec![
+        Value::Record(vec![("name".to_string(), Value::Union(0, Box::new(Value::Null)))]),
+        Value::Record(vec![(
+            "name".to_string(),
+            Value::Union(1, Box::new(Value::String("a".to_string()))),
+        )]),
+    ];
+
+    let mut builder = avroarrow::create_builder(&schema, 32).unwrap();
+    for v in &values {
+        avroarrow::append_record_resolved(&mut builder, &schema, &schema, v).unwrap();
+    }
+
+    let actual = builder.as_any_mut().downcast_mut::<StructBuilder>().unwrap().finish();
+
+    assert_eq!(actual.column(0).as_ref(), &StringArray::from(vec![None, Some("a")]));
+}
+
+#[test]
+fn test_append_record_resolved_int_to_long_promotion() {
+    let writer = Schema::Int;
+    let reader = Schema::Long;
+    let values = vec![Value::Int(3), Value::Int(4), Value::Int(5)];
+
+    let expected = to_array::<i64, Int64Array>(vec![3, 4, 5]);
+    let actual = to_arrow_resolved::<Int64Builder>(&writer, &reader, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_record_resolved_int_to_double_promotion() {
+    let writer = Schema::Int;
+    let reader = Schema::Double;
+    let values = vec![Value::Int(3), Value::Int(4)];
+
+    let expected = to_array::<f64, Float64Array>(vec![3.0, 4.0]);
+    let actual = to_arrow_resolved::<Float64Builder>(&writer, &reader, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_record_resolved_string_to_bytes_promotion() {
+    let writer = Schema::String;
+    let reader = Schema::Bytes;
+    let values = vec![Value::String("abc".to_string())];
+
+    let raw: Vec<&[u8]> = vec![b"abc"];
+    let expected: ArrayRef = Arc::new(BinaryArray::from_vec(raw));
+    let actual = to_arrow_resolved::<BinaryBuilder>(&writer, &reader, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_record_resolved_bytes_to_string_promotion() {
+    let writer = Schema::Bytes;
+    let reader = Schema::String;
+    let values = vec![Value::Bytes(b"abc".to_vec())];
+
+    let expected = to_array::<String, StringArray>(vec!["abc".to_string()]);
+    let actual = to_arrow_resolved::<StringBuilder>(&writer, &reader, &values);
+
+    assert_eq!(*expected, *actual);
+}
+
+#[test]
+fn test_append_record_resolved_enum_unknown_symbol_falls_back_to_default() {
+    let writer_enum = Schema::Enum(EnumSchema {
+        name: fixed_name("color"),
+        doc: None,
+        aliases: None,
+        symbols: vec!["RED".to_string(), "GREEN".to_string(), "BLUE".to_string()],
+        default: None,
+        attributes: Default::default(),
+    });
+    let reader_enum = Schema::Enum(EnumSchema {
+        name: fixed_name("color"),
+        doc: None,
+        aliases: None,
+        symbols: vec!["RED".to_string(), "GREEN".to_string()],
+        default: Some("RED".to_string()),
+        attributes: Default::default(),
+    });
+
+    let values = vec![
+        Value::Enum(1, "GREEN".to_string()),
+        Value::Enum(2, "BLUE".to_string()),
+    ];
+
+    let expected_keys = Int32Array::from(vec![1, 0]);
+    let expected_values: ArrayRef = Arc::new(StringArray::from(vec!["RED", "GREEN"]));
+    let expected = DictionaryArray::<Int32Type>::try_new(expected_keys, expected_values).unwrap();
+
+    let actual = to_arrow_resolved::<avroarrow::dictionary::EnumDictionaryBuilder>(
+        &writer_enum,
+        &reader_enum,
+        &values,
+    );
+
+    assert_eq!(expected, *actual.as_any().downcast_ref::<DictionaryArray<Int32Type>>().unwrap());
+}
+
+#[test]
+fn test_append_record_resolved_union_branch_picks_promotable_variant() {
+    let writer = Schema::Int;
+    let reader = Schema::Union(UnionSchema::new(vec![
+        Schema::String,
+        Schema::Long,
+    ]).unwrap());
+
+    let values = vec![Value::Int(7)];
+
+    let fields = UnionFields::new(
+        vec![0, 1],
+        vec![
+            Field::new("string_0", DataType::Utf8, false),
+            Field::new("long_1", DataType::Int64, false),
+        ],
+    );
+    let type_ids = vec![1i8];
+    let offsets = vec![0i32];
+    let children: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(Vec::<&str>::new())),
+        Arc::new(Int64Array::from(vec![7])),
+    ];
+    let expected = UnionArray::try_new(fields, type_ids.into(), Some(offsets.into()), children).unwrap();
+
+    let actual = to_arrow_resolved::<avroarrow::union::DenseUnionBuilder>(&writer, &reader, &values);
+
+    assert_eq!(expected, *actual.as_any().downcast_ref::<UnionArray>().unwrap());
+}
+
+#[test]
+fn test_append_record_resolved_union_branch_rejects_unrelated_named_schema() {
+    let writer = Schema::Enum(EnumSchema {
+        name: fixed_name("e1"),
+        doc: None,
+        aliases: None,
+        symbols: vec!["X".to_string()],
+        default: None,
+        attributes: Default::default(),
+    });
+    let reader = Schema::Union(UnionSchema::new(vec![
+        Schema::Enum(EnumSchema {
+            name: fixed_name("e2"),
+            doc: None,
+            aliases: None,
+            symbols: vec!["Y".to_string()],
+            default: None,
+            attributes: Default::default(),
+        }),
+        Schema::String,
+    ]).unwrap());
+
+    let mut builder = avroarrow::create_builder(&reader, 32).unwrap();
+    let err = avroarrow::append_record_resolved(
+        &mut builder,
+        &writer,
+        &reader,
+        &Value::Enum(0, "X".to_string()),
+    );
+
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_append_union_multi_with_null() {
+    let schema = Schema::Union(UnionSchema::new(vec![
+        Schema::Null,
+        Schema::Int,
+        Schema::String,
+    ]).unwrap());
+
+    let values = vec![
+        Value::Union(0, Box::new(Value::Null)),
+        Value::Union(1, Box::new(Value::Int(5))),
+        Value::Union(2, Box::new(Value::String("x".to_string()))),
+    ];
+
+    // The leading `null` branch has no union child of its own: it folds
+    // into the first non-null variant's array as a null value instead, so
+    // only two children -- keyed by their original variant index, 1 and 2
+    // -- ever show up in `UnionFields`.
+    let fields = UnionFields::new(
+        vec![1, 2],
+        vec![
+            Field::new("int_1", DataType::Int32, false),
+            Field::new("string_2", DataType::Utf8, false),
+        ],
+    );
+    let type_ids = vec![1i8, 1, 2];
+    let offsets = vec![0i32, 1, 0];
+    let children: Vec<ArrayRef> = vec![
+        Arc::new(Int32Array::from(vec![None, Some(5)])),
+        Arc::new(StringArray::from(vec!["x"])),
+    ];
+    let expected = UnionArray::try_new(fields, type_ids.into(), Some(offsets.into()), children).unwrap();
+
+    let actual = to_arrow::<avroarrow::union::DenseUnionBuilder>(&schema, &values);
+
+    assert_eq!(expected, *actual.as_any().downcast_ref::<UnionArray>().unwrap());
+}
@@ -45,7 +45,7 @@ fn test_convert_schema_flat() {
         Field::new("f_uuid", DataType::FixedSizeBinary(16), false),
         Field::new("f_dec128", DataType::Decimal128(38, 8), false),
         Field::new("f_opt_date", DataType::Date32, true),
-        Field::new("f_duration", DataType::Duration(TimeUnit::Millisecond), false),
+        Field::new("f_duration", DataType::Interval(IntervalUnit::MonthDayNano), false),
         Field::new("f_bytes", DataType::Binary, false),
         Field::new("f_opt_fixed", DataType::FixedSizeBinary(32), true),
         Field::new("f_time_ms", DataType::Time32(TimeUnit::Millisecond), false),
@@ -117,3 +117,96 @@ fn test_convert_schema_nested() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn test_convert_arrow_schema_flat() {
+    let arrow_schema = Schema::new(vec![
+        Field::new("f_bool", DataType::Boolean, false),
+        Field::new("f_int", DataType::Int32, false),
+        Field::new("f_long", DataType::Int64, false),
+        Field::new("f_float", DataType::Float32, false),
+        Field::new("f_double", DataType::Float64, false),
+        Field::new("f_string", DataType::Utf8, true),
+        Field::new("f_bytes", DataType::Binary, false),
+        Field::new("f_dec128", DataType::Decimal128(10, 4), false),
+        Field::new("f_array", array_of!(DataType::Utf8, false), false),
+        Field::new("f_map", map_of!(DataType::Int64), false),
+        Field::new(
+            "f_record",
+            DataType::Struct(vec![Field::new("inner", DataType::Utf8, false)].into()),
+            false,
+        ),
+    ]);
+
+    let expected = AvroSchema::parse_str(r#"{
+        "type": "record",
+        "name": "Envelope",
+        "fields": [
+            {"name": "f_bool", "type": "boolean"},
+            {"name": "f_int", "type": "int"},
+            {"name": "f_long", "type": "long"},
+            {"name": "f_float", "type": "float"},
+            {"name": "f_double", "type": "double"},
+            {"name": "f_string", "type": ["null", "string"]},
+            {"name": "f_bytes", "type": "bytes"},
+            {"name": "f_dec128", "type": {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 4}},
+            {"name": "f_array", "type": {"type": "array", "items": "string"}},
+            {"name": "f_map", "type": {"type": "map", "values": "long"}},
+            {"name": "f_record", "type": {"type": "record", "name": "f_record_record", "fields": [
+                {"name": "inner", "type": "string"}
+            ]}}
+        ]
+    }"#).unwrap();
+
+    let actual = avroarrow::convert_arrow_schema(&arrow_schema).unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_convert_arrow_schema_logical_types() {
+    let arrow_schema = Schema::new(vec![
+        Field::new("f_uuid", DataType::FixedSizeBinary(16), false),
+        Field::new("f_fixed", DataType::FixedSizeBinary(4), false),
+        Field::new("f_date", DataType::Date32, false),
+        Field::new("f_time_ms", DataType::Time32(TimeUnit::Millisecond), false),
+        Field::new("f_time_mc", DataType::Time64(TimeUnit::Microsecond), false),
+        Field::new("f_timestamp_ms", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+        Field::new("f_loc_timestamp_mc", DataType::Timestamp(TimeUnit::Microsecond, Some(tz_offset!())), false),
+        Field::new("f_dec256", DataType::Decimal256(50, 10), false),
+    ]);
+
+    let expected = AvroSchema::parse_str(r#"{
+        "type": "record",
+        "name": "Envelope",
+        "fields": [
+            {"name": "f_uuid", "type": {"type": "string", "logicalType": "uuid"}},
+            {"name": "f_fixed", "type": {"type": "fixed", "name": "f_fixed_fixed", "size": 4}},
+            {"name": "f_date", "type": {"type": "int", "logicalType": "date"}},
+            {"name": "f_time_ms", "type": {"type": "int", "logicalType": "time-millis"}},
+            {"name": "f_time_mc", "type": {"type": "long", "logicalType": "time-micros"}},
+            {"name": "f_timestamp_ms", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "f_loc_timestamp_mc", "type": {"type": "long", "logicalType": "local-timestamp-micros"}},
+            {"name": "f_dec256", "type": {"type": "bytes", "logicalType": "decimal", "precision": 50, "scale": 10}}
+        ]
+    }"#).unwrap();
+
+    let actual = avroarrow::convert_arrow_schema(&arrow_schema).unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_convert_arrow_schema_enum_dictionary_errors() {
+    let arrow_schema = Schema::new(vec![
+        Field::new(
+            "f_enum",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        ),
+    ]);
+
+    let actual = avroarrow::convert_arrow_schema(&arrow_schema);
+
+    assert!(actual.is_err());
+}
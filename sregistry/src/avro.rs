@@ -40,6 +40,7 @@ pub struct AvroRegistry {
     client: Client,
     cache: HashMap<SchemaKey, Rc<Schema>>,
     cache_raw: HashMap<SchemaKey, String>,
+    cache_by_id: HashMap<i32, Rc<Schema>>,
 }
 
 impl AvroRegistry {
@@ -48,9 +49,65 @@ impl AvroRegistry {
             client,
             cache: HashMap::new(),
             cache_raw: HashMap::new(),
+            cache_by_id: HashMap::new(),
         }
     }
 
+    pub fn get_by_id(&mut self, id: i32) -> Result<Rc<Schema>, RegistryError> {
+        if let Some(schema) = self.cache_by_id.get(&id) {
+            return Ok(schema.clone());
+        }
+
+        let response = self.client.get_schema_by_id(id)
+            .map_err(|e| RegistryError::ClientError(e.to_string()))?;
+
+        let mut dep_schemas: Vec<(SchemaKey, String)> = Vec::new();
+        if let Some(refs) = response.references {
+            for dep in refs {
+                self.resolve(&dep.subject, dep.version, &mut dep_schemas)?;
+            }
+        }
+
+        let mut schemas_raw: Vec<&str> = vec![response.schema.as_str()];
+        schemas_raw.extend(dep_schemas.iter().map(|(_, value)| value.as_str()));
+
+        let avro_schemas = Schema::parse_list(&schemas_raw)
+            .map_err(|e| RegistryError::DeserializationFailed(e.to_string()))?;
+
+        let mut tmp_cache: HashMap<Name, Schema> = HashMap::new();
+        for s in &avro_schemas {
+            register_schema(s, &mut tmp_cache)?;
+        }
+
+        let expanded = expand_schema(&avro_schemas[0], &tmp_cache)?;
+        let schema = Rc::new(expanded);
+        self.cache_by_id.insert(id, schema.clone());
+
+        Ok(schema)
+    }
+
+    /// Decodes the Confluent wire format: a magic `0x00` byte, a big-endian
+    /// 4-byte global schema id, then the Avro-encoded payload. Resolves the
+    /// id through [`AvroRegistry::get_by_id`] and returns the schema paired
+    /// with the remaining payload slice.
+    pub fn decode_wire_format<'a>(&mut self, frame: &'a [u8]) -> Result<(Rc<Schema>, &'a [u8]), RegistryError> {
+        if frame.len() < 5 {
+            return Err(RegistryError::ResolutionFailed(
+                "Confluent wire format frame is too short".to_string(),
+            ));
+        }
+        if frame[0] != 0x00 {
+            return Err(RegistryError::ResolutionFailed(format!(
+                "unexpected wire format magic byte {:#x}", frame[0],
+            )));
+        }
+
+        let id = i32::from_be_bytes([frame[1], frame[2], frame[3], frame[4]]);
+        let schema = self.get_by_id(id)?;
+
+        Ok((schema, &frame[5..]))
+    }
+
     pub fn get(
         &mut self,
         subject: &str,
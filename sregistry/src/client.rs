@@ -35,6 +35,14 @@ struct SubjectResponse {
 #[serde(rename_all = "camelCase")]
 struct VersionsResponse(Vec<i32>);
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaById {
+    pub schema: String,
+    pub schema_type: Option<String>,
+    pub references: Option<Vec<Reference>>,
+}
+
 pub struct Client {
     client: HttpClient,
     url: String,
@@ -50,6 +58,11 @@ fn sr_schema_url(base_url: &str, subject: &str, version: i32) -> String {
     format!("{}/subjects/{}-value/versions/{}", base_url, subject, version)
 }
 
+#[inline]
+fn sr_schema_by_id_url(base_url: &str, id: i32) -> String {
+    format!("{}/schemas/ids/{}", base_url, id)
+}
+
 impl Client {
     pub fn new(client: HttpClient, url: String) -> Self {
         Self { client, url }
@@ -125,4 +138,36 @@ impl Client {
             }
         }
     }
+
+    pub fn get_schema_by_id(
+        &self,
+        id: i32,
+    ) -> Result<SchemaById, Box<dyn Error>> {
+        info!(
+            registry_url = self.url,
+            id = id,
+            "fetching schema by id from schema registry",
+        );
+
+        let url = sr_schema_by_id_url(&self.url, id);
+        debug!(
+            url = url,
+            "performing HTTP GET",
+        );
+        let res = self.client.get(&url).send()?;
+
+        match res.status() {
+            reqwest::StatusCode::OK => {
+                Ok(res.json::<SchemaById>()?)
+            },
+            _ => {
+                error!(
+                    url = url,
+                    status = res.status().as_u16(),
+                    "HTTP request failed",
+                );
+                Err(format!("failed to get schema for id '{}'", id).into())
+            }
+        }
+    }
 }
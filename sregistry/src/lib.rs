@@ -4,6 +4,8 @@ pub mod client;
 pub use client::Client;
 pub use client::Subject;
 pub use client::Reference;
+pub use client::SchemaById;
+pub use avro::AvroRegistry;
 
 #[derive(Debug)]
 pub enum RegistryError {
@@ -12,3 +14,16 @@ pub enum RegistryError {
     ResolutionFailed(String),
     DeserializationFailed(String),
 }
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::ExpectedRecord => write!(f, "expected a record schema"),
+            RegistryError::ClientError(msg) => write!(f, "registry client error: {}", msg),
+            RegistryError::ResolutionFailed(msg) => write!(f, "schema resolution failed: {}", msg),
+            RegistryError::DeserializationFailed(msg) => write!(f, "schema deserialization failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
@@ -1,3 +1,150 @@
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_avro_registry_get_by_id() {
+    let user_schema = r#"
+        {
+            "type": "record",
+            "name": "User",
+            "fields": [
+                {
+                  "name": "id",
+                  "type": "long"
+                }
+            ]
+        }
+    "#;
+
+    let mut server = mockito::Server::new();
+
+    let _m_schema = server
+        .mock("GET", "/schemas/ids/42")
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "schema": user_schema,
+                "schemaType": "AVRO",
+            })
+            .to_string(),
+        )
+        .create();
+
+    let client = sregistry::Client::new(reqwest::blocking::Client::new(), server.url());
+    let mut instance = sregistry::avro::AvroRegistry::new(client);
+
+    let expected = apache_avro::Schema::parse_str(user_schema).unwrap();
+    let actual = instance.get_by_id(42).unwrap();
+
+    assert_eq!(expected, *actual);
+}
+
+#[test]
+fn test_avro_registry_get_by_id_caches_result() {
+    let user_schema = r#"
+        {
+            "type": "record",
+            "name": "User",
+            "fields": [
+                {
+                  "name": "id",
+                  "type": "long"
+                }
+            ]
+        }
+    "#;
+
+    let mut server = mockito::Server::new();
+
+    let _m_schema = server
+        .mock("GET", "/schemas/ids/42")
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "schema": user_schema,
+                "schemaType": "AVRO",
+            })
+            .to_string(),
+        )
+        .create();
+
+    let client = sregistry::Client::new(reqwest::blocking::Client::new(), server.url());
+    let mut instance = sregistry::avro::AvroRegistry::new(client);
+
+    let first = instance.get_by_id(42).unwrap();
+    let second = instance.get_by_id(42).unwrap();
+
+    // `get_by_id` caches by id: a second lookup returns the same `Rc`
+    // without issuing another HTTP request (only one mock was registered).
+    assert!(std::rc::Rc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn test_decode_wire_format_ok() {
+    let user_schema = r#"
+        {
+            "type": "record",
+            "name": "User",
+            "fields": [
+                {
+                  "name": "id",
+                  "type": "long"
+                }
+            ]
+        }
+    "#;
+
+    let mut server = mockito::Server::new();
+
+    let _m_schema = server
+        .mock("GET", "/schemas/ids/7")
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "schema": user_schema,
+                "schemaType": "AVRO",
+            })
+            .to_string(),
+        )
+        .create();
+
+    let client = sregistry::Client::new(reqwest::blocking::Client::new(), server.url());
+    let mut instance = sregistry::avro::AvroRegistry::new(client);
+
+    let mut frame = vec![0x00u8];
+    frame.extend_from_slice(&7i32.to_be_bytes());
+    frame.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+    let expected = apache_avro::Schema::parse_str(user_schema).unwrap();
+    let (schema, payload) = instance.decode_wire_format(&frame).unwrap();
+
+    assert_eq!(expected, *schema);
+    assert_eq!(payload, &[0xde, 0xad, 0xbe, 0xef]);
+}
+
+#[test]
+fn test_decode_wire_format_too_short() {
+    let client = sregistry::Client::new(reqwest::blocking::Client::new(), "http://localhost".to_string());
+    let mut instance = sregistry::avro::AvroRegistry::new(client);
+
+    let frame = vec![0x00u8, 0x00, 0x00, 0x00];
+
+    let res = instance.decode_wire_format(&frame);
+    assert!(res.is_err(), "expected error but got ok");
+}
+
+#[test]
+fn test_decode_wire_format_bad_magic_byte() {
+    let client = sregistry::Client::new(reqwest::blocking::Client::new(), "http://localhost".to_string());
+    let mut instance = sregistry::avro::AvroRegistry::new(client);
+
+    let mut frame = vec![0x01u8];
+    frame.extend_from_slice(&7i32.to_be_bytes());
+    frame.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+    let res = instance.decode_wire_format(&frame);
+    assert!(res.is_err(), "expected error but got ok");
+}
+
 #[test]
 fn test_avro_registry_get_with_references() {
     let location_schema = r#"
@@ -174,6 +174,67 @@ fn test_get_schema_ok() {
     );
 }
 
+#[test]
+fn test_get_schema_by_id_ok() {
+    let schema = r#"
+        {
+            "type": "record",
+            "name": "User",
+            "fields": [
+                {
+                  "name": "id",
+                  "type": "long"
+                }
+            ]
+        }
+    "#;
+
+    let mut server = mockito::Server::new();
+
+    let _m_schema = server
+        .mock("GET", "/schemas/ids/5")
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "schema": schema,
+                "schemaType": "AVRO",
+            })
+            .to_string(),
+        )
+        .create();
+
+    let client = reqwest::blocking::Client::new();
+    let instance = sregistry::Client::new(client, server.url());
+
+    assert_eq!(
+        sregistry::SchemaById {
+            schema: schema.to_string(),
+            schema_type: Some("AVRO".to_string()),
+            references: None,
+        },
+        instance.get_schema_by_id(5).unwrap(),
+    );
+}
+
+#[test]
+fn test_get_schema_by_id_404() {
+    let mut server = mockito::Server::new();
+
+    let _m_schema = server
+        .mock("GET", "/schemas/ids/5")
+        .with_status(404)
+        .with_body(serde_json::json!({
+            "detail": "not found"
+        }).to_string())
+        .create();
+
+    let client = reqwest::blocking::Client::new();
+    let registry = sregistry::Client::new(client, server.url());
+
+    let res = registry.get_schema_by_id(5);
+    assert!(res.is_err(), "expected error but got ok");
+}
+
 #[test]
 fn test_get_schema_404() {
     let mut server = mockito::Server::new();